@@ -0,0 +1,157 @@
+use crate::error::ApiError;
+use crate::models::{BookLevel, MarketDetails, OrderRequest, OrderResponse};
+use anyhow::{anyhow, Result};
+use rust_decimal::Decimal;
+use std::str::FromStr;
+
+const CLOB_BASE_URL: &str = "https://clob.polymarket.com";
+
+/// Thin REST client over Polymarket's CLOB API
+pub struct PolymarketApi {
+    client: reqwest::Client,
+    base_url: String,
+}
+
+impl PolymarketApi {
+    pub fn new() -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            base_url: CLOB_BASE_URL.to_string(),
+        }
+    }
+
+    /// Fetch the best BUY (ask) or SELL (bid) price for a token
+    ///
+    /// Returns a structured `ApiError` so callers can tell a transient rate-limit/5xx apart
+    /// from a genuinely missing market instead of treating every failure identically.
+    pub async fn get_price(&self, token_id: &str, side: &str) -> Result<Decimal, ApiError> {
+        let url = format!("{}/price", self.base_url);
+        let resp = self
+            .client
+            .get(&url)
+            .query(&[("token_id", token_id), ("side", side)])
+            .send()
+            .await?;
+
+        let status = resp.status();
+        if status == reqwest::StatusCode::TOO_MANY_REQUESTS {
+            return Err(ApiError::RateLimited);
+        }
+        if status == reqwest::StatusCode::NOT_FOUND {
+            return Err(ApiError::NotFound);
+        }
+        if status.is_server_error() {
+            return Err(ApiError::ServerError(status.as_u16()));
+        }
+
+        let body: serde_json::Value = resp.json().await?;
+        let price = body
+            .get("price")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| ApiError::MalformedResponse("missing price field".to_string()))?;
+
+        Decimal::from_str(price)
+            .map_err(|e| ApiError::MalformedResponse(format!("malformed price '{}': {}", price, e)))
+    }
+
+    /// Fetch ask-side order-book depth for a token, best price first
+    pub async fn get_book(&self, token_id: &str) -> Result<Vec<BookLevel>> {
+        let url = format!("{}/book", self.base_url);
+        let resp = self
+            .client
+            .get(&url)
+            .query(&[("token_id", token_id)])
+            .send()
+            .await?;
+
+        let body: serde_json::Value = resp.json().await?;
+        let asks = body
+            .get("asks")
+            .and_then(|v| v.as_array())
+            .ok_or_else(|| anyhow!("missing asks field in book response"))?;
+
+        let mut levels = Vec::with_capacity(asks.len());
+        for level in asks {
+            let price = level
+                .get("price")
+                .and_then(|v| v.as_str())
+                .and_then(|s| Decimal::from_str(s).ok());
+            let size = level
+                .get("size")
+                .and_then(|v| v.as_str())
+                .and_then(|s| Decimal::from_str(s).ok());
+
+            if let (Some(price), Some(size)) = (price, size) {
+                levels.push(BookLevel { price, size });
+            }
+        }
+
+        Ok(levels)
+    }
+
+    /// Fetch usable USDC balance available to open new positions with
+    pub async fn get_balance(&self) -> Result<Decimal> {
+        let url = format!("{}/balance", self.base_url);
+        let resp = self.client.get(&url).send().await?;
+
+        let body: serde_json::Value = resp.json().await?;
+        let balance = body
+            .get("balance")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| anyhow!("missing balance field in balance response"))?;
+
+        Decimal::from_str(balance)
+            .map_err(|e| anyhow!("malformed balance '{}': {}", balance, e))
+    }
+
+    /// Fetch full market details (resolution state + per-outcome tokens) for a condition
+    pub async fn get_market(&self, condition_id: &str) -> Result<MarketDetails> {
+        let url = format!("{}/markets/{}", self.base_url, condition_id);
+        let resp = self.client.get(&url).send().await?;
+        let market = resp.json::<MarketDetails>().await?;
+        Ok(market)
+    }
+
+    /// Place an order against the CLOB
+    pub async fn place_order(&self, order: &OrderRequest) -> Result<OrderResponse> {
+        let url = format!("{}/order", self.base_url);
+        let resp = self.client.post(&url).json(order).send().await?;
+        let response = resp.json::<OrderResponse>().await?;
+        Ok(response)
+    }
+}
+
+impl Default for PolymarketApi {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Retry a fallible request a few times with exponential backoff, but only for transient
+/// error classes (rate-limit/server-error/transport); a terminal error like `NotFound`
+/// returns immediately so callers can react to it rather than waste the retry budget.
+pub async fn retry_with_backoff<T, F, Fut>(max_attempts: u32, mut request: F) -> Result<T, ApiError>
+where
+    F: FnMut() -> Fut,
+    Fut: std::future::Future<Output = Result<T, ApiError>>,
+{
+    let mut attempt = 0;
+    loop {
+        match request().await {
+            Ok(value) => return Ok(value),
+            Err(e) if e.is_transient() && attempt + 1 < max_attempts => {
+                let backoff_ms = 200u64 * 2u64.pow(attempt);
+                log::debug!(
+                    "transient error ({}), retrying in {}ms (attempt {}/{})",
+                    e,
+                    backoff_ms,
+                    attempt + 1,
+                    max_attempts
+                );
+                tokio::time::sleep(std::time::Duration::from_millis(backoff_ms)).await;
+                attempt += 1;
+            }
+            Err(e) => return Err(e),
+        }
+    }
+}
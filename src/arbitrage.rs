@@ -6,53 +6,52 @@ use rust_decimal_macros::dec;
 #[derive(Clone)]
 pub struct ArbitrageDetector {
     min_profit_threshold: Decimal,
+    // Fraction applied to each leg's ask to model fees/slippage, e.g. 0.02 = 2%
+    spread: Decimal,
 }
 
 impl ArbitrageDetector {
     pub fn new(min_profit_threshold: f64) -> Self {
+        Self::with_spread(min_profit_threshold, 0.02)
+    }
+
+    pub fn with_spread(min_profit_threshold: f64, spread: f64) -> Self {
         Self {
             min_profit_threshold: Decimal::from_f64_retain(min_profit_threshold)
                 .unwrap_or(dec!(0.01)),
+            spread: Decimal::from_f64_retain(spread).unwrap_or(dec!(0.02)),
         }
     }
 
-    /// Detect arbitrage opportunities between ETH and BTC markets
-    /// Strategy: Buy Up token in ETH market + Buy Down token in BTC market
-    /// when total cost < $1
+    /// Detect arbitrage opportunities across every monitored market.
+    /// Enumerates every pair of outcome tokens present in the snapshot - including both
+    /// outcomes of the *same* market, which is the true single-market arbitrage case of
+    /// buying UP and DOWN together for a guaranteed $1 payout - and fires whenever their
+    /// combined (spread-adjusted) cost clears `min_profit_threshold` below $1. New markets
+    /// just need an entry in `MarketSnapshot::markets`; no detector changes required.
     pub fn detect_opportunities(&self, snapshot: &MarketSnapshot) -> Vec<ArbitrageOpportunity> {
-        let mut opportunities = Vec::new();
-
-        // Get prices from both markets
-        let eth_up = snapshot.eth_market.up_token.as_ref();
-        let eth_down = snapshot.eth_market.down_token.as_ref();
-        let btc_up = snapshot.btc_market.up_token.as_ref();
-        let btc_down = snapshot.btc_market.down_token.as_ref();
-
-        // Strategy 1: ETH Up + BTC Down
-        if let (Some(eth_up_price), Some(btc_down_price)) = (eth_up, btc_down) {
-            if let Some(opportunity) = self.check_arbitrage(
-                eth_up_price,
-                btc_down_price,
-                &snapshot.eth_market.condition_id,
-                &snapshot.btc_market.condition_id,
-                "ETH_UP",
-                "BTC_DOWN",
-            ) {
-                opportunities.push(opportunity);
+        let mut candidates = Vec::new();
+        for market in &snapshot.markets {
+            if let Some(token) = &market.up_token {
+                candidates.push((&market.market_name, "UP", &market.condition_id, token));
+            }
+            if let Some(token) = &market.down_token {
+                candidates.push((&market.market_name, "DOWN", &market.condition_id, token));
             }
         }
 
-        // Strategy 2: ETH Down + BTC Up
-        if let (Some(eth_down_price), Some(btc_up_price)) = (eth_down, btc_up) {
-            if let Some(opportunity) = self.check_arbitrage(
-                eth_down_price,
-                btc_up_price,
-                &snapshot.eth_market.condition_id,
-                &snapshot.btc_market.condition_id,
-                "ETH_DOWN",
-                "BTC_UP",
-            ) {
-                opportunities.push(opportunity);
+        let mut opportunities = Vec::new();
+        for i in 0..candidates.len() {
+            for j in (i + 1)..candidates.len() {
+                let (name_a, outcome_a, condition_a, token_a) = candidates[i];
+                let (name_b, outcome_b, condition_b, token_b) = candidates[j];
+
+                if let Some(opportunity) = self.check_arbitrage(
+                    (name_a, outcome_a, condition_a, token_a),
+                    (name_b, outcome_b, condition_b, token_b),
+                ) {
+                    opportunities.push(opportunity);
+                }
             }
         }
 
@@ -61,45 +60,156 @@ impl ArbitrageDetector {
 
     fn check_arbitrage(
         &self,
-        token1: &TokenPrice,
-        token2: &TokenPrice,
-        _condition1: &str,
-        _condition2: &str,
-        _label1: &str,
-        _label2: &str,
+        leg_a: (&str, &str, &str, &TokenPrice),
+        leg_b: (&str, &str, &str, &TokenPrice),
     ) -> Option<ArbitrageOpportunity> {
-        let price1 = token1.ask_price();
-        let price2 = token2.ask_price();
-        let total_cost = price1 + price2;
+        let (name_a, outcome_a, condition_a, token_a) = leg_a;
+        let (name_b, outcome_b, condition_b, token_b) = leg_b;
+
+        let price_a = token_a.ask_price();
+        let price_b = token_b.ask_price();
         let dollar = dec!(1.0);
         let min_price_threshold = dec!(0.6);
 
-        // Safety filter: Don't trade if both tokens are below $0.6 (rug case)
-        // This avoids cases where both markets might go against us
-        if price1 < min_price_threshold && price2 < min_price_threshold {
+        // Safety filter: don't trade a cross-market pair if both tokens are below $0.6 (rug
+        // case) - this avoids cases where both markets might go against us. Same-market
+        // UP+DOWN pairs are exempt: they pay out $1 by construction (exactly one outcome
+        // wins), so a genuine single-market arb sits with both legs near $0.48-$0.49 - well
+        // below this threshold - and isn't a rug risk the way two unrelated markets would be.
+        if condition_a != condition_b
+            && price_a < min_price_threshold
+            && price_b < min_price_threshold
+        {
             return None;
         }
 
-        // Check if total cost is less than $1
+        // Inflate each leg by the configured spread to model fees/slippage, so an
+        // opportunity only fires when the worst-case filled cost still clears the bar
+        let effective_a = price_a * (dec!(1.0) + self.spread);
+        let effective_b = price_b * (dec!(1.0) + self.spread);
+        let total_cost = effective_a + effective_b;
+
+        // Check if worst-case total cost is less than $1
         if total_cost < dollar {
             let expected_profit = dollar - total_cost;
-            
+
             // Only return if profit meets threshold
             if expected_profit >= self.min_profit_threshold {
+                let (max_size, blended_cost) = self
+                    .size_against_depth(&token_a.ask_levels, &token_b.ask_levels)
+                    .map_or((None, None), |(size, cost)| (Some(size), Some(cost)));
+
                 return Some(ArbitrageOpportunity {
-                    eth_up_price: price1,
-                    btc_down_price: price2,
+                    leg_a: Leg {
+                        market_name: name_a.to_string(),
+                        outcome: outcome_a.to_string(),
+                        token_id: token_a.token_id.clone(),
+                        condition_id: condition_a.to_string(),
+                        price: price_a,
+                    },
+                    leg_b: Leg {
+                        market_name: name_b.to_string(),
+                        outcome: outcome_b.to_string(),
+                        token_id: token_b.token_id.clone(),
+                        condition_id: condition_b.to_string(),
+                        price: price_b,
+                    },
                     total_cost,
                     expected_profit,
-                    eth_up_token_id: token1.token_id.clone(),
-                    btc_down_token_id: token2.token_id.clone(),
-                    eth_condition_id: _condition1.to_string(),
-                    btc_condition_id: _condition2.to_string(),
+                    max_size,
+                    blended_cost,
                 });
             }
         }
 
         None
     }
+
+    /// Walk both legs' ask ladders simultaneously, greedily consuming matched quantity
+    /// level-by-level, stopping once the marginal combined cost of the next unit would no
+    /// longer clear `min_profit_threshold`. Returns the maximum executable size and the
+    /// blended total cost of filling it, or `None` if either book is empty or not even the
+    /// first matched level clears the threshold.
+    fn size_against_depth(
+        &self,
+        leg1_levels: &[BookLevel],
+        leg2_levels: &[BookLevel],
+    ) -> Option<(Decimal, Decimal)> {
+        let dollar = dec!(1.0);
+        let mut i = 0usize;
+        let mut j = 0usize;
+        let mut remaining1 = leg1_levels.first()?.size;
+        let mut remaining2 = leg2_levels.first()?.size;
+        let mut total_size = dec!(0);
+        let mut total_cost = dec!(0);
+
+        while i < leg1_levels.len() && j < leg2_levels.len() {
+            let price1 = leg1_levels[i].price * (dec!(1.0) + self.spread);
+            let price2 = leg2_levels[j].price * (dec!(1.0) + self.spread);
+            let marginal_cost = price1 + price2;
+
+            if dollar - marginal_cost < self.min_profit_threshold {
+                break;
+            }
+
+            let fill_qty = remaining1.min(remaining2);
+            if fill_qty <= dec!(0) {
+                break;
+            }
+
+            total_size += fill_qty;
+            total_cost += marginal_cost * fill_qty;
+            remaining1 -= fill_qty;
+            remaining2 -= fill_qty;
+
+            if remaining1 <= dec!(0) {
+                i += 1;
+                remaining1 = leg1_levels.get(i).map(|l| l.size).unwrap_or(dec!(0));
+            }
+            if remaining2 <= dec!(0) {
+                j += 1;
+                remaining2 = leg2_levels.get(j).map(|l| l.size).unwrap_or(dec!(0));
+            }
+        }
+
+        if total_size > dec!(0) {
+            Some((total_size, total_cost))
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn size_against_depth_stops_once_marginal_cost_breaches_threshold() {
+        let detector = ArbitrageDetector::with_spread(0.01, 0.0);
+
+        let leg_a_levels = vec![
+            BookLevel { price: dec!(0.40), size: dec!(10) },
+            BookLevel { price: dec!(0.70), size: dec!(100) },
+        ];
+        let leg_b_levels = vec![
+            BookLevel { price: dec!(0.55), size: dec!(10) },
+            BookLevel { price: dec!(0.55), size: dec!(100) },
+        ];
+
+        // First level: 0.40 + 0.55 = 0.95, profit 0.05 clears the 0.01 threshold - fills the
+        // full 10 units. Second level: 0.70 + 0.55 = 1.25, over a dollar - the walk must stop
+        // there instead of continuing to consume the deeper, losing level.
+        let result = detector.size_against_depth(&leg_a_levels, &leg_b_levels);
+
+        assert_eq!(result, Some((dec!(10), dec!(9.5))));
+    }
+
+    #[test]
+    fn size_against_depth_returns_none_on_empty_book() {
+        let detector = ArbitrageDetector::with_spread(0.01, 0.0);
+
+        assert_eq!(detector.size_against_depth(&[], &[]), None);
+    }
 }
 
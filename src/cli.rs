@@ -0,0 +1,79 @@
+use crate::trader::Trader;
+use clap::Parser;
+use std::sync::Arc;
+use tokio::io::{AsyncBufReadExt, BufReader};
+
+/// Operator commands for inspecting and intervening on a running `Trader` - read as lines
+/// from stdin while the monitor loop runs concurrently in the same process, so `stats`/
+/// `positions`/`settle`/`cancel` act on live state without a separate daemon or IPC.
+#[derive(Parser, Debug)]
+#[command(name = "", no_binary_name = true)]
+enum Command {
+    /// Print total profit and trade count
+    Stats,
+    /// List every open position with its state, age, units, and investment
+    Positions,
+    /// Force-settle a position immediately, bypassing the 14-minute age gate
+    Settle { key: String },
+    /// Remove a stuck position without settling it
+    Cancel { key: String },
+}
+
+/// Read operator commands from stdin until it closes, dispatching each to `trader`
+pub async fn run(trader: Arc<Trader>) {
+    let mut lines = BufReader::new(tokio::io::stdin()).lines();
+
+    loop {
+        let line = match lines.next_line().await {
+            Ok(Some(line)) => line,
+            Ok(None) => break,
+            Err(e) => {
+                log::warn!("Failed to read operator command: {}", e);
+                continue;
+            }
+        };
+
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let command = match Command::try_parse_from(line.split_whitespace()) {
+            Ok(command) => command,
+            Err(e) => {
+                println!("{}", e);
+                continue;
+            }
+        };
+
+        match command {
+            Command::Stats => {
+                let (total_profit, trades_executed) = trader.get_stats().await;
+                println!("Total profit: ${:.2} | Trades executed: {}", total_profit, trades_executed);
+            }
+            Command::Positions => {
+                let positions = trader.list_positions().await;
+                if positions.is_empty() {
+                    println!("No open positions");
+                }
+                for position in positions {
+                    println!(
+                        "{} | state: {} | age: {}s | units: {:.2} | investment: ${:.2}",
+                        position.key,
+                        position.state,
+                        position.age.as_secs(),
+                        position.units,
+                        position.investment_amount
+                    );
+                }
+            }
+            Command::Settle { key } => match trader.force_settle(&key).await {
+                Ok(()) => println!("Settled {}", key),
+                Err(e) => println!("Failed to settle {}: {}", key, e),
+            },
+            Command::Cancel { key } => match trader.cancel_position(&key).await {
+                Ok(()) => println!("Cancelled {}", key),
+                Err(e) => println!("Failed to cancel {}: {}", key, e),
+            },
+        }
+    }
+}
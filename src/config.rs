@@ -0,0 +1,43 @@
+/// How `Trader::calculate_position_size` turns an opportunity's edge into a dollar amount
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SizingStrategy {
+    /// Deploy `max_position_size` (capped at usable balance), regardless of the edge's size
+    #[default]
+    Flat,
+    /// Scale the committed amount with the realized edge (`expected_profit / total_cost`), so
+    /// capital concentrates on the fattest spreads and stays small on marginal ones
+    FractionalKelly,
+}
+
+/// Runtime-tunable trading parameters
+#[derive(Debug, Clone)]
+pub struct TradingConfig {
+    pub max_position_size: f64,
+    // Floor on a committed position's total investment - below this, order/network fees eat
+    // the arbitrage edge. Opportunities that don't clear it are accumulated, not fired.
+    pub min_position_size: f64,
+    // Floor below which a single opportunity's ideal size isn't even worth accumulating
+    // toward `min_position_size` (e.g. `total_cost` sitting right at $1)
+    pub dust_threshold: f64,
+    pub min_profit_threshold: f64,
+    // Fraction applied to each leg's ask price to model fees/slippage, e.g. 0.02 = 2%
+    pub ask_spread: f64,
+    pub sizing_strategy: SizingStrategy,
+    // Applied to the realized edge before clamping to get the Kelly fraction; only used by
+    // `SizingStrategy::FractionalKelly`
+    pub kelly_multiplier: f64,
+}
+
+impl Default for TradingConfig {
+    fn default() -> Self {
+        Self {
+            max_position_size: 100.0,
+            min_position_size: 10.0,
+            dust_threshold: 1.0,
+            min_profit_threshold: 0.01,
+            ask_spread: 0.02,
+            sizing_strategy: SizingStrategy::Flat,
+            kelly_multiplier: 5.0,
+        }
+    }
+}
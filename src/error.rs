@@ -0,0 +1,27 @@
+use thiserror::Error;
+
+/// Structured classification of a price-fetch failure, analogous to how an exchange client
+/// matches on numeric response codes to tell a retryable blip from a permanent failure.
+#[derive(Debug, Error)]
+pub enum ApiError {
+    #[error("rate limited (HTTP 429)")]
+    RateLimited,
+    #[error("server error (HTTP {0})")]
+    ServerError(u16),
+    #[error("malformed response: {0}")]
+    MalformedResponse(String),
+    #[error("not found")]
+    NotFound,
+    #[error("transport error: {0}")]
+    Transport(#[from] reqwest::Error),
+}
+
+impl ApiError {
+    /// Whether retrying the same request with backoff is worth attempting
+    pub fn is_transient(&self) -> bool {
+        matches!(
+            self,
+            ApiError::RateLimited | ApiError::ServerError(_) | ApiError::Transport(_)
+        )
+    }
+}
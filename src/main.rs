@@ -0,0 +1,88 @@
+mod api;
+mod arbitrage;
+mod cli;
+mod config;
+mod error;
+mod models;
+mod monitor;
+mod store;
+mod trader;
+mod ws;
+
+use anyhow::Result;
+use api::PolymarketApi;
+use arbitrage::ArbitrageDetector;
+use config::TradingConfig;
+use log::info;
+use models::Market;
+use monitor::{MarketMonitor, MonitorMode};
+use std::sync::Arc;
+use std::time::Duration;
+use trader::Trader;
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    env_logger::init();
+
+    // Drains open positions to Settled/Failed without opening new ones - useful during
+    // deploys or reconfiguration when you don't want to commit to fresh arbitrage trades.
+    let resume_only = std::env::args().any(|arg| arg == "--resume-only");
+
+    let api = Arc::new(PolymarketApi::new());
+    let config = TradingConfig::default();
+    let detector = ArbitrageDetector::with_spread(config.min_profit_threshold, config.ask_spread);
+    let trader = Arc::new(Trader::new(api.clone(), config, true, resume_only)?);
+    trader.settle_resumed_trades().await?;
+
+    let eth_market = Market {
+        condition_id: String::new(),
+        slug: "eth-up-down".to_string(),
+    };
+    let btc_market = Market {
+        condition_id: String::new(),
+        slug: "btc-up-down".to_string(),
+    };
+
+    let monitor = MarketMonitor::new(
+        api,
+        eth_market,
+        btc_market,
+        1000,
+        detector,
+        MonitorMode::Live,
+    );
+
+    info!("Starting polymarket-arbitrage-bot-btc-eth-15m...");
+
+    // Operator commands (stats/positions/settle/cancel) read from stdin alongside the
+    // monitor loop, so the bot can be inspected and intervened on without a separate daemon.
+    tokio::spawn(cli::run(trader.clone()));
+
+    // `settle_resumed_trades` above only drains whatever was on disk at startup - without this,
+    // nothing ever ticks a trade's lifecycle forward again, so fills would sit open forever.
+    {
+        let trader = trader.clone();
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(Duration::from_secs(30));
+            loop {
+                interval.tick().await;
+                if let Err(e) = trader.check_pending_trades().await {
+                    log::warn!("Failed to check pending trades: {}", e);
+                }
+            }
+        });
+    }
+
+    monitor
+        .start_monitoring(move |opportunity| {
+            let trader = trader.clone();
+            async move {
+                if let Err(e) = trader.execute_arbitrage(&opportunity).await {
+                    log::warn!("Failed to execute arbitrage: {}", e);
+                }
+            }
+        })
+        .await;
+
+    Ok(())
+}
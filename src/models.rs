@@ -0,0 +1,209 @@
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use strum::Display;
+
+/// A 15-minute binary market (e.g. "ETH Up/Down 3:00-3:15pm") as returned by market discovery
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Market {
+    pub condition_id: String,
+    pub slug: String,
+}
+
+/// A single outcome token within a market, as returned by `get_market`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MarketToken {
+    pub token_id: String,
+    pub outcome: String,
+    #[serde(default)]
+    pub winner: bool,
+}
+
+/// Full market details, including resolution state and per-outcome tokens
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MarketDetails {
+    pub condition_id: String,
+    #[serde(default)]
+    pub closed: bool,
+    pub tokens: Vec<MarketToken>,
+}
+
+/// A single order-book level: a price and the size available at it
+#[derive(Debug, Clone, Copy)]
+pub struct BookLevel {
+    pub price: Decimal,
+    pub size: Decimal,
+}
+
+/// Last-known bid/ask for a single outcome token, plus order-book depth when available
+#[derive(Debug, Clone)]
+pub struct TokenPrice {
+    pub token_id: String,
+    pub bid: Option<Decimal>,
+    pub ask: Option<Decimal>,
+    // Ask-side depth, best price first. Empty when only a top-of-book quote was fetched.
+    pub ask_levels: Vec<BookLevel>,
+}
+
+impl TokenPrice {
+    /// Price to buy this token (what a taker pays to cross the ask)
+    pub fn ask_price(&self) -> Decimal {
+        self.ask.unwrap_or(dec!(1.0))
+    }
+
+    /// Price to sell this token (what a taker receives crossing the bid)
+    pub fn bid_price(&self) -> Decimal {
+        self.bid.unwrap_or(dec!(0.0))
+    }
+}
+
+/// Snapshot of both legs of a market pair at a point in time
+#[derive(Debug, Clone)]
+pub struct MarketData {
+    pub condition_id: String,
+    pub market_name: String,
+    pub up_token: Option<TokenPrice>,
+    pub down_token: Option<TokenPrice>,
+}
+
+/// One side of an arbitrage pair: a specific outcome token in a specific market
+#[derive(Debug, Clone)]
+pub struct Leg {
+    pub market_name: String,
+    pub outcome: String,
+    pub token_id: String,
+    pub condition_id: String,
+    pub price: Decimal,
+}
+
+/// A detected arbitrage opportunity: buy `leg_a` + `leg_b` for a combined cost below $1.
+/// `leg_a`/`leg_b` may be two outcomes of the *same* market (guaranteed $1 payout) or of
+/// two different markets (cross-asset arbitrage) - the detector doesn't care which.
+#[derive(Debug, Clone)]
+pub struct ArbitrageOpportunity {
+    pub leg_a: Leg,
+    pub leg_b: Leg,
+    pub total_cost: Decimal,
+    pub expected_profit: Decimal,
+    // Depth-aware sizing: the largest size executable on both legs simultaneously, and the
+    // blended total cost of filling it. `None` when only top-of-book quotes were available, or
+    // when no depth level clears `min_profit_threshold`.
+    pub max_size: Option<Decimal>,
+    pub blended_cost: Option<Decimal>,
+}
+
+/// Explicit lifecycle of an arbitrage position, driven one step at a time by
+/// `Trader::advance`. Replaces inferring progress from `timestamp.elapsed()` and ad hoc
+/// booleans, and makes a partial fill (one leg succeeds, the other fails) a first-class
+/// `Failed` state instead of silently creating a balanced `PendingTrade`.
+#[derive(Debug, Clone, PartialEq, Display, Serialize, Deserialize)]
+pub enum TradeState {
+    /// Orders just submitted, fills not yet confirmed
+    Open,
+    /// Both legs filled
+    Filled,
+    /// Filled and waiting for the 14-minute settlement window
+    AwaitingSettlement,
+    /// Window elapsed and both markets report closed; settling now
+    Settling,
+    /// Settlement complete
+    Settled { profit: f64 },
+    /// Terminal failure (e.g. one leg filled, the other didn't)
+    Failed { reason: String },
+}
+
+/// An open arbitrage position, tracked through `state` as both legs' markets resolve
+#[derive(Debug, Clone)]
+pub struct PendingTrade {
+    pub leg_a_token_id: String,
+    pub leg_b_token_id: String,
+    pub leg_a_condition_id: String,
+    pub leg_b_condition_id: String,
+    pub investment_amount: f64,
+    pub units: f64,
+    pub timestamp: Instant,
+    pub state: TradeState,
+}
+
+/// Operator-facing view of one `PendingTrade`, returned by `Trader::list_positions`
+#[derive(Debug, Clone)]
+pub struct PositionSummary {
+    pub key: String,
+    pub state: TradeState,
+    pub age: std::time::Duration,
+    pub units: f64,
+    pub investment_amount: f64,
+}
+
+/// Serializable mirror of `PendingTrade` for `TradeStore`. `Instant` isn't `Serialize` - it's
+/// only meaningful within the process that created it - so persistence tracks wall-clock age
+/// instead, and `Trader` reconstructs an equivalent `Instant` when loading a trade back in.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PersistedTrade {
+    pub leg_a_token_id: String,
+    pub leg_b_token_id: String,
+    pub leg_a_condition_id: String,
+    pub leg_b_condition_id: String,
+    pub investment_amount: f64,
+    pub units: f64,
+    pub opened_at_unix_secs: u64,
+    pub state: TradeState,
+}
+
+impl From<&PendingTrade> for PersistedTrade {
+    fn from(trade: &PendingTrade) -> Self {
+        let age = trade.timestamp.elapsed();
+        let opened_at_unix_secs = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .saturating_sub(age)
+            .as_secs();
+
+        Self {
+            leg_a_token_id: trade.leg_a_token_id.clone(),
+            leg_b_token_id: trade.leg_b_token_id.clone(),
+            leg_a_condition_id: trade.leg_a_condition_id.clone(),
+            leg_b_condition_id: trade.leg_b_condition_id.clone(),
+            investment_amount: trade.investment_amount,
+            units: trade.units,
+            opened_at_unix_secs,
+            state: trade.state.clone(),
+        }
+    }
+}
+
+impl From<PersistedTrade> for PendingTrade {
+    fn from(persisted: PersistedTrade) -> Self {
+        let opened_at = UNIX_EPOCH + Duration::from_secs(persisted.opened_at_unix_secs);
+        let age = SystemTime::now()
+            .duration_since(opened_at)
+            .unwrap_or_default();
+
+        Self {
+            leg_a_token_id: persisted.leg_a_token_id,
+            leg_b_token_id: persisted.leg_b_token_id,
+            leg_a_condition_id: persisted.leg_a_condition_id,
+            leg_b_condition_id: persisted.leg_b_condition_id,
+            investment_amount: persisted.investment_amount,
+            units: persisted.units,
+            timestamp: Instant::now().checked_sub(age).unwrap_or_else(Instant::now),
+            state: persisted.state,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct OrderRequest {
+    pub token_id: String,
+    pub side: String,
+    pub size: String,
+    pub price: String,
+    pub order_type: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct OrderResponse {
+    pub order_id: String,
+    pub status: String,
+}
@@ -1,14 +1,32 @@
-use crate::api::PolymarketApi;
+use crate::api::{retry_with_backoff, PolymarketApi};
+use crate::arbitrage::ArbitrageDetector;
+use crate::error::ApiError;
 use crate::models::*;
+use crate::ws::PriceStream;
 use anyhow::Result;
 use log::{debug, info, warn};
 use std::sync::Arc;
+use tokio::sync::mpsc;
 use tokio::time::{sleep, Duration};
 
+/// Whether detected opportunities are handed to the execution callback or only logged.
+/// `DetectOnly` lets an operator validate detection and tune thresholds against live
+/// markets without risking capital, and is also a safe state to idle in while a new
+/// 15-minute period's markets are being discovered.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MonitorMode {
+    Live,
+    DetectOnly,
+}
+
+#[derive(Clone)]
 pub struct MarketMonitor {
     api: Arc<PolymarketApi>,
     eth_market: Arc<tokio::sync::Mutex<crate::models::Market>>,
     btc_market: Arc<tokio::sync::Mutex<crate::models::Market>>,
+    price_stream: Arc<PriceStream>,
+    detector: ArbitrageDetector,
+    mode: Arc<tokio::sync::Mutex<MonitorMode>>,
     check_interval: Duration,
     // Cached token IDs from getMarket() - refreshed once per period
     eth_up_token_id: Arc<tokio::sync::Mutex<Option<String>>>,
@@ -21,8 +39,9 @@ pub struct MarketMonitor {
 
 #[derive(Debug, Clone)]
 pub struct MarketSnapshot {
-    pub eth_market: MarketData,
-    pub btc_market: MarketData,
+    // One entry per monitored market (ETH, BTC, and any future 15-minute market added to
+    // `MarketMonitor`); the detector enumerates leg-pairs across all of them generically.
+    pub markets: Vec<MarketData>,
     pub timestamp: std::time::Instant,
 }
 
@@ -32,6 +51,8 @@ impl MarketMonitor {
         eth_market: crate::models::Market,
         btc_market: crate::models::Market,
         check_interval_ms: u64,
+        detector: ArbitrageDetector,
+        mode: MonitorMode,
     ) -> Self {
         // Calculate current 15-minute period timestamp
         let current_time = std::time::SystemTime::now()
@@ -44,6 +65,9 @@ impl MarketMonitor {
             api,
             eth_market: Arc::new(tokio::sync::Mutex::new(eth_market)),
             btc_market: Arc::new(tokio::sync::Mutex::new(btc_market)),
+            price_stream: Arc::new(PriceStream::new()),
+            detector,
+            mode: Arc::new(tokio::sync::Mutex::new(mode)),
             check_interval: Duration::from_millis(check_interval_ms),
             eth_up_token_id: Arc::new(tokio::sync::Mutex::new(None)),
             eth_down_token_id: Arc::new(tokio::sync::Mutex::new(None)),
@@ -95,6 +119,11 @@ impl MarketMonitor {
         current_period != stored_period
     }
 
+    /// Switch between detect-only and live modes without restarting the monitor loop
+    pub async fn set_mode(&self, mode: MonitorMode) {
+        *self.mode.lock().await = mode;
+    }
+
     /// Get current market condition IDs (for checking if markets are closed)
     pub async fn get_current_condition_ids(&self) -> (String, String) {
         let eth = self.eth_market.lock().await.condition_id.clone();
@@ -165,13 +194,30 @@ impl MarketMonitor {
         let btc_up_token_id = self.btc_up_token_id.lock().await.clone();
         let btc_down_token_id = self.btc_down_token_id.lock().await.clone();
         
-        let (eth_up_price, eth_down_price, btc_up_price, btc_down_price) = tokio::join!(
+        let (eth_up, eth_down, btc_up, btc_down) = tokio::join!(
             self.fetch_token_price(&eth_up_token_id, "ETH", "Up"),
             self.fetch_token_price(&eth_down_token_id, "ETH", "Down"),
             self.fetch_token_price(&btc_up_token_id, "BTC", "Up"),
             self.fetch_token_price(&btc_down_token_id, "BTC", "Down"),
         );
 
+        for (label, result) in [
+            ("ETH Up", &eth_up),
+            ("ETH Down", &eth_down),
+            ("BTC Up", &btc_up),
+            ("BTC Down", &btc_down),
+        ] {
+            if let Some(err) = &result.1 {
+                match err {
+                    ApiError::NotFound => warn!("{} market appears closed: {}", label, err),
+                    _ => warn!("{} API degraded, skipping this cycle: {}", label, err),
+                }
+            }
+        }
+
+        let (eth_up_price, eth_down_price, btc_up_price, btc_down_price) =
+            (eth_up.0, eth_down.0, btc_up.0, btc_down.0);
+
         let eth_market_data = MarketData {
             condition_id: eth_condition_id,
             market_name: "ETH".to_string(),
@@ -187,23 +233,65 @@ impl MarketMonitor {
         };
 
         Ok(MarketSnapshot {
-            eth_market: eth_market_data,
-            btc_market: btc_market_data,
+            markets: vec![eth_market_data, btc_market_data],
             timestamp: std::time::Instant::now(),
         })
     }
 
+    /// Current cached token_ids, in a stable order, for WebSocket subscription
+    async fn cached_token_ids(&self) -> Vec<String> {
+        let ids = [
+            self.eth_up_token_id.lock().await.clone(),
+            self.eth_down_token_id.lock().await.clone(),
+            self.btc_up_token_id.lock().await.clone(),
+            self.btc_down_token_id.lock().await.clone(),
+        ];
+        ids.into_iter().flatten().collect()
+    }
+
+    /// Keep a live WebSocket subscription to the cached token_ids, reconnecting with a
+    /// fixed backoff on drop. Pushes a notification on `tick_tx` for every price update so
+    /// `start_monitoring` can react immediately instead of waiting for the next poll.
+    async fn run_price_stream(&self, tick_tx: mpsc::Sender<String>) {
+        loop {
+            let token_ids = self.cached_token_ids().await;
+            if token_ids.is_empty() {
+                sleep(Duration::from_secs(1)).await;
+                continue;
+            }
+
+            if let Err(e) = self.price_stream.run(&token_ids, tick_tx.clone()).await {
+                warn!("Market websocket stream error, reconnecting: {}", e);
+            }
+
+            sleep(Duration::from_secs(2)).await;
+        }
+    }
+
+    /// Fetch one token's price, retrying transient failures with exponential backoff.
+    /// Returns the price when available, and the terminal `ApiError` kind when it isn't, so
+    /// callers can tell "market closed" (`NotFound`) apart from "API degraded" (everything else).
     async fn fetch_token_price(
         &self,
         token_id: &Option<String>,
         market_name: &str,
         outcome: &str,
-    ) -> Option<TokenPrice> {
-        let token_id = token_id.as_ref()?;
+    ) -> (Option<TokenPrice>, Option<ApiError>) {
+        let Some(token_id) = token_id.as_ref() else {
+            return (None, None);
+        };
+
+        // Prefer the live WebSocket price; REST is only the fallback for discovery/gap-fill
+        if let Some(price) = self.price_stream.last_price(token_id).await {
+            return (Some(price), None);
+        }
+
+        const MAX_ATTEMPTS: u32 = 3;
 
         // Get BUY price (ask price - what we pay to buy)
-        let buy_price = match self.api.get_price(token_id, "BUY").await {
-            Ok(price) => Some(price),
+        let buy_result = retry_with_backoff(MAX_ATTEMPTS, || self.api.get_price(token_id, "BUY")).await;
+        let buy_price = match &buy_result {
+            Ok(price) => Some(*price),
             Err(e) => {
                 warn!("Failed to fetch {} {} BUY price: {}", market_name, outcome, e);
                 None
@@ -211,47 +299,99 @@ impl MarketMonitor {
         };
 
         // Get SELL price (bid price - what we get when selling)
-        let sell_price = match self.api.get_price(token_id, "SELL").await {
-            Ok(price) => Some(price),
+        let sell_result =
+            retry_with_backoff(MAX_ATTEMPTS, || self.api.get_price(token_id, "SELL")).await;
+        let sell_price = match &sell_result {
+            Ok(price) => Some(*price),
             Err(e) => {
                 warn!("Failed to fetch {} {} SELL price: {}", market_name, outcome, e);
                 None
             }
         };
 
-        if buy_price.is_some() || sell_price.is_some() {
+        if buy_price.is_none() && sell_price.is_none() {
+            // Prefer the BUY leg's error as the terminal kind surfaced upward
+            let terminal = buy_result.err().or_else(|| sell_result.err());
+            return (None, terminal);
+        }
+
+        // Best-effort depth for position sizing; a failed/empty book just means the
+        // opportunity gets sized against top-of-book only.
+        let ask_levels = self.api.get_book(token_id).await.unwrap_or_default();
+
+        (
             Some(TokenPrice {
                 token_id: token_id.clone(),
                 bid: sell_price,
                 ask: buy_price,
-            })
-        } else {
-            None
-        }
+                ask_levels,
+            }),
+            None,
+        )
     }
 
 
-    /// Start monitoring markets continuously
-    /// Returns a callback function that can be used to update markets when new period starts
-    pub async fn start_monitoring<F, Fut>(&self, callback: F)
+    /// Start monitoring markets continuously, running `detect_opportunities` on every
+    /// snapshot. In `MonitorMode::Live`, each opportunity is handed to `on_opportunity` for
+    /// execution; in `MonitorMode::DetectOnly` opportunities are only logged and the
+    /// execution callback is never invoked, so the bot can run against real markets without
+    /// risking capital.
+    pub async fn start_monitoring<F, Fut>(&self, on_opportunity: F)
     where
-        F: Fn(MarketSnapshot) -> Fut + Send + Sync + 'static,
+        F: Fn(ArbitrageOpportunity) -> Fut + Send + Sync + 'static,
         Fut: std::future::Future<Output = ()> + Send + 'static,
     {
         info!("Starting market monitoring...");
-        
+
+        // Live push updates drive `detect_opportunities` as prices move; the REST poll
+        // below still runs on `check_interval` as a gap-fill in case the stream is down.
+        let (tick_tx, mut tick_rx) = mpsc::channel(256);
+        {
+            let monitor = self.clone();
+            tokio::spawn(async move {
+                monitor.run_price_stream(tick_tx).await;
+            });
+        }
+
         loop {
-            match self.fetch_market_data().await {
-                Ok(snapshot) => {
-                    debug!("Market snapshot updated");
-                    callback(snapshot).await;
+            tokio::select! {
+                Some(_) = tick_rx.recv() => {
+                    self.process_snapshot("websocket push", &on_opportunity).await;
                 }
-                Err(e) => {
-                    warn!("Error fetching market data: {}", e);
+                _ = sleep(self.check_interval) => {
+                    self.process_snapshot("poll fallback", &on_opportunity).await;
+                }
+            }
+        }
+    }
+
+    async fn process_snapshot<F, Fut>(&self, source: &str, on_opportunity: &F)
+    where
+        F: Fn(ArbitrageOpportunity) -> Fut + Send + Sync + 'static,
+        Fut: std::future::Future<Output = ()> + Send + 'static,
+    {
+        let snapshot = match self.fetch_market_data().await {
+            Ok(snapshot) => snapshot,
+            Err(e) => {
+                warn!("Error fetching market data ({}): {}", source, e);
+                return;
+            }
+        };
+        debug!("Market snapshot updated from {}", source);
+
+        let mode = *self.mode.lock().await;
+        for opportunity in self.detector.detect_opportunities(&snapshot) {
+            info!(
+                "🎯 Opportunity detected (mode: {:?}): total_cost=${:.4} expected_profit=${:.4}",
+                mode, opportunity.total_cost, opportunity.expected_profit
+            );
+
+            match mode {
+                MonitorMode::Live => on_opportunity(opportunity).await,
+                MonitorMode::DetectOnly => {
+                    debug!("Detect-only mode: not routing opportunity to execution");
                 }
             }
-            
-            sleep(self.check_interval).await;
         }
     }
 }
@@ -0,0 +1,47 @@
+use crate::models::{PendingTrade, PersistedTrade};
+use anyhow::Result;
+use log::warn;
+
+/// Durable on-disk record of open positions. Without this, a crash or restart between trade
+/// entry and the 14-minute settlement window permanently orphans a `PendingTrade` - in
+/// production mode the winning leg's tokens would never get sold. Backed by a single `sled`
+/// tree, keyed identically to the in-memory `pending_trades` map
+/// (`leg_a_condition_id + "_" + leg_b_condition_id`, with a `_partial_<token_id>` suffix for
+/// a one-legged fill recorded as `Failed`).
+pub struct TradeStore {
+    db: sled::Db,
+}
+
+impl TradeStore {
+    pub fn open(path: &str) -> Result<Self> {
+        Ok(Self {
+            db: sled::open(path)?,
+        })
+    }
+
+    pub fn save(&self, key: &str, trade: &PendingTrade) -> Result<()> {
+        let persisted = PersistedTrade::from(trade);
+        self.db.insert(key, serde_json::to_vec(&persisted)?)?;
+        Ok(())
+    }
+
+    pub fn remove(&self, key: &str) -> Result<()> {
+        self.db.remove(key)?;
+        Ok(())
+    }
+
+    /// Load every persisted trade, keyed the same way as `pending_trades`. A corrupt entry is
+    /// logged and skipped rather than failing the whole rehydration.
+    pub fn load_all(&self) -> Result<Vec<(String, PendingTrade)>> {
+        let mut trades = Vec::new();
+        for entry in self.db.iter() {
+            let (key, bytes) = entry?;
+            let key = String::from_utf8_lossy(&key).into_owned();
+            match serde_json::from_slice::<PersistedTrade>(&bytes) {
+                Ok(persisted) => trades.push((key, persisted.into())),
+                Err(e) => warn!("Skipping corrupt persisted trade {}: {}", key, e),
+            }
+        }
+        Ok(trades)
+    }
+}
@@ -1,6 +1,7 @@
 use crate::api::PolymarketApi;
 use crate::models::*;
-use crate::config::TradingConfig;
+use crate::config::{SizingStrategy, TradingConfig};
+use crate::store::TradeStore;
 use anyhow::Result;
 use log::{info, warn, debug};
 use rust_decimal::Decimal;
@@ -9,115 +10,248 @@ use tokio::sync::Mutex;
 use std::collections::HashMap;
 use std::time::{Instant, Duration};
 
+const TRADE_STORE_PATH: &str = "data/pending_trades";
+
 #[derive(Clone)]
 struct CachedMarketData {
     market: MarketDetails,
     cached_at: Instant,
 }
 
+// Per-market cache slot. Holding this entry's own lock across the in-flight `get_market`
+// request (rather than just the outer map's lock) is what dedups concurrent callers for the
+// same `condition_id`: the first caller keeps the lock held while it awaits the request, so a
+// second caller for the same market blocks on the same lock and reuses the result instead of
+// firing its own redundant request. Different markets use different entries and never block
+// each other.
+#[derive(Default)]
+struct CachedMarketEntry {
+    data: Option<CachedMarketData>,
+}
+
 pub struct Trader {
     api: Arc<PolymarketApi>,
     config: TradingConfig,
     simulation_mode: bool,
+    // When true, `execute_arbitrage` is a no-op - an operator can drain existing positions to
+    // `Settled`/`Failed` via the normal settlement path without opening new ones during a
+    // deploy or reconfiguration.
+    resume_only: bool,
     total_profit: Arc<Mutex<f64>>,
     trades_executed: Arc<Mutex<u64>>,
-    pending_trades: Arc<Mutex<HashMap<String, PendingTrade>>>, // Key: eth_condition_id + btc_condition_id
-    market_cache: Arc<Mutex<HashMap<String, CachedMarketData>>>, // Key: condition_id, cache for 60 seconds
+    pending_trades: Arc<Mutex<HashMap<String, PendingTrade>>>, // Key: leg_a_condition_id + leg_b_condition_id (+ "_partial_<token_id>" for a one-legged fill)
+    market_cache: Arc<Mutex<HashMap<String, Arc<Mutex<CachedMarketEntry>>>>>, // Key: condition_id, cache for 60 seconds
+    queued_investment: Arc<Mutex<HashMap<String, f64>>>, // Key: same as pending_trades, waiting on min_position_size
+    store: TradeStore,
 }
 
 impl Trader {
-    pub fn new(api: Arc<PolymarketApi>, config: TradingConfig, simulation_mode: bool) -> Self {
-        Self {
+    /// Opens the durable trade store at `TRADE_STORE_PATH` and rehydrates `pending_trades`
+    /// from whatever was persisted there, so a restart between trade entry and settlement
+    /// doesn't orphan an open position. Call `settle_resumed_trades` afterwards to advance
+    /// anything that's already past its settlement window.
+    pub fn new(api: Arc<PolymarketApi>, config: TradingConfig, simulation_mode: bool, resume_only: bool) -> Result<Self> {
+        let store = TradeStore::open(TRADE_STORE_PATH)?;
+        let resumed = store.load_all()?;
+        if !resumed.is_empty() {
+            info!("Resuming {} pending trade(s) from disk", resumed.len());
+        }
+
+        Ok(Self {
             api,
             config,
             simulation_mode,
+            resume_only,
             total_profit: Arc::new(Mutex::new(0.0)),
             trades_executed: Arc::new(Mutex::new(0)),
-            pending_trades: Arc::new(Mutex::new(HashMap::new())),
+            pending_trades: Arc::new(Mutex::new(resumed.into_iter().collect())),
             market_cache: Arc::new(Mutex::new(HashMap::new())),
+            queued_investment: Arc::new(Mutex::new(HashMap::new())),
+            store,
+        })
+    }
+
+    /// Drive every trade rehydrated from disk forward until none of them make further
+    /// progress, so anything already past its 14-minute window settles immediately on startup
+    /// instead of waiting for the first periodic `check_pending_trades` tick. `advance` already walks a
+    /// single trade to a terminal/gated state per call, so this only needs to keep re-running
+    /// `check_pending_trades` while a pass still changes at least one trade's state (e.g. a
+    /// freshly-closed market unblocking a trade that was stuck in `AwaitingSettlement`).
+    pub async fn settle_resumed_trades(&self) -> Result<()> {
+        loop {
+            let states_before = self.pending_trade_states().await;
+            self.check_pending_trades().await?;
+            let states_after = self.pending_trade_states().await;
+
+            if states_before == states_after {
+                return Ok(());
+            }
         }
     }
 
-    /// Check and settle pending trades when markets close
+    async fn pending_trade_states(&self) -> HashMap<String, TradeState> {
+        self.pending_trades
+            .lock()
+            .await
+            .iter()
+            .map(|(key, trade)| (key.clone(), trade.state.clone()))
+            .collect()
+    }
+
+    /// Drive every pending trade's lifecycle forward via `advance`, as far as it can go this
+    /// tick (to a terminal state or the next gate). A trade that reaches
+    /// `Settled` is folded into `total_profit` and removed; a `Failed` trade is logged and
+    /// left in place for operator inspection/intervention rather than silently dropped.
     pub async fn check_pending_trades(&self) -> Result<()> {
         let mut pending = self.pending_trades.lock().await;
         let mut to_remove = Vec::new();
-        
-        // Only check trades that are at least 14 minutes old (markets close after 15 minutes)
-        let min_age = Duration::from_secs(14 * 60);
-        
+
         let pending_count = pending.len();
         if pending_count > 0 {
-            debug!("Checking {} pending trades for market closure...", pending_count);
-        }
-        
-        for (key, trade) in pending.iter() {
-            let age = trade.timestamp.elapsed();
-            
-            // Skip checking if trade is too recent (markets won't be closed yet)
-            if age < min_age {
-                debug!("Trade {} is too recent (age: {:.1}s, need: {:.1}s), skipping", 
-                       key, age.as_secs_f64(), min_age.as_secs_f64());
-                continue;
+            debug!("Advancing {} pending trades...", pending_count);
+        }
+
+        for (key, trade) in pending.iter_mut() {
+            let previous_state = trade.state.clone();
+            let next_state = self.advance(trade).await;
+
+            if next_state != previous_state {
+                info!("Trade {} transitioned {} -> {}", key, previous_state, next_state);
+                // Keep the on-disk `state` in step with the in-memory one - otherwise a
+                // resumed trade would look frozen at `Open` on disk even though `advance`
+                // had long since moved it through `Filled`/`AwaitingSettlement`/etc.
+                self.persist(key, trade);
             }
-            
-            info!("🔍 Checking market closure for trade {} (age: {:.1} minutes)", 
-                  key, age.as_secs_f64() / 60.0);
-            
-            // Check if markets are closed (using cached data when possible)
-            let (eth_closed, eth_winner) = self.check_market_result_cached(&trade.eth_condition_id, &trade.eth_token_id).await?;
-            let (btc_closed, btc_winner) = self.check_market_result_cached(&trade.btc_condition_id, &trade.btc_token_id).await?;
-            
-            info!("   ETH Market ({}): closed={}, winner={}", 
-                  &trade.eth_condition_id[..16], eth_closed, eth_winner);
-            info!("   BTC Market ({}): closed={}, winner={}", 
-                  &trade.btc_condition_id[..16], btc_closed, btc_winner);
-            
-            if eth_closed && btc_closed {
-                // Both markets closed, sell/redeem winning tokens and calculate actual profit
-                if !self.simulation_mode {
-                    // In production mode, try to sell winning tokens (they're worth $1 each)
-                    self.sell_winning_tokens(&trade, eth_winner, btc_winner).await;
+
+            match &next_state {
+                TradeState::Settled { profit } => {
+                    let mut total = self.total_profit.lock().await;
+                    *total += profit;
+                    let total_profit = *total;
+                    drop(total);
+
+                    info!(
+                        "💰 Trade {} settled | Actual Profit: ${:.4} | Total Profit: ${:.2}",
+                        key, profit, total_profit
+                    );
+                    to_remove.push(key.clone());
                 }
-                
-                let actual_profit = self.calculate_actual_profit(&trade, eth_winner, btc_winner);
-                
-                let mut total = self.total_profit.lock().await;
-                *total += actual_profit;
-                let total_profit = *total;
-                drop(total);
-                
-                info!(
-                    "💰 Market Closed - ETH Winner: {}, BTC Winner: {} | Actual Profit: ${:.4} | Total Profit: ${:.2}",
-                    if eth_winner { "WON" } else { "LOST" },
-                    if btc_winner { "WON" } else { "LOST" },
-                    actual_profit,
-                    total_profit
-                );
-                
-                to_remove.push(key.clone());
-            } else {
-                info!("   ⏳ Markets not both closed yet (ETH: {}, BTC: {}), will check again...", 
-                      eth_closed, btc_closed);
+                TradeState::Failed { reason } => {
+                    warn!("⚠️  Trade {} failed: {} (left for manual review)", key, reason);
+                }
+                _ => {}
             }
         }
-        
+
         for key in to_remove {
             pending.remove(&key);
+            if let Err(e) = self.store.remove(&key) {
+                warn!("Failed to remove settled trade {} from disk: {}", key, e);
+            }
         }
-        
+
         Ok(())
     }
 
+    /// Best-effort persist of the current state of `key`'s trade; a failure here is logged
+    /// but doesn't fail the caller, since the in-memory `pending_trades` entry is already the
+    /// source of truth for this process's lifetime.
+    fn persist(&self, key: &str, trade: &PendingTrade) {
+        if let Err(e) = self.store.save(key, trade) {
+            warn!("Failed to persist trade {} to disk: {}", key, e);
+        }
+    }
+
+    /// Drive `trade`'s state forward until it reaches a terminal state or stalls on a gate
+    /// (e.g. `AwaitingSettlement` waiting out the 14-minute window). Each `advance_once` step
+    /// only performs one transition, so without this loop a trade that's actually ready to
+    /// settle would need ~4 separate `check_pending_trades` ticks to get there.
+    async fn advance(&self, trade: &mut PendingTrade) -> TradeState {
+        loop {
+            let next_state = self.advance_once(trade).await;
+            if next_state == trade.state {
+                return next_state;
+            }
+            trade.state = next_state.clone();
+            if matches!(next_state, TradeState::Settled { .. } | TradeState::Failed { .. }) {
+                return next_state;
+            }
+        }
+    }
+
+    /// Compute the next `TradeState` for `trade`. Each transition is driven by a single
+    /// condition: `Open`->`Filled` once both legs are confirmed filled (already true by the
+    /// time a trade is tracked, since a partial fill is inserted directly as `Failed`),
+    /// `Filled`->`AwaitingSettlement` immediately, `AwaitingSettlement`->`Settling` once age
+    /// >= 14 minutes and both markets report closed, `Settling`->`Settled` after selling the
+    /// winning leg(s) and computing actual profit.
+    async fn advance_once(&self, trade: &PendingTrade) -> TradeState {
+        match &trade.state {
+            TradeState::Open => TradeState::Filled,
+            TradeState::Filled => TradeState::AwaitingSettlement,
+            TradeState::AwaitingSettlement => {
+                let min_age = Duration::from_secs(14 * 60);
+                if trade.timestamp.elapsed() < min_age {
+                    return TradeState::AwaitingSettlement;
+                }
+
+                let (leg_a_closed, _) = self
+                    .check_market_result_cached(&trade.leg_a_condition_id, &trade.leg_a_token_id)
+                    .await
+                    .unwrap_or((false, false));
+                let (leg_b_closed, _) = self
+                    .check_market_result_cached(&trade.leg_b_condition_id, &trade.leg_b_token_id)
+                    .await
+                    .unwrap_or((false, false));
+
+                if leg_a_closed && leg_b_closed {
+                    TradeState::Settling
+                } else {
+                    TradeState::AwaitingSettlement
+                }
+            }
+            TradeState::Settling => {
+                let (_, leg_a_winner) = self
+                    .check_market_result_cached(&trade.leg_a_condition_id, &trade.leg_a_token_id)
+                    .await
+                    .unwrap_or((false, false));
+                let (_, leg_b_winner) = self
+                    .check_market_result_cached(&trade.leg_b_condition_id, &trade.leg_b_token_id)
+                    .await
+                    .unwrap_or((false, false));
+
+                if !self.simulation_mode {
+                    // In production mode, try to sell winning tokens (they're worth $1 each)
+                    self.sell_winning_tokens(trade, leg_a_winner, leg_b_winner).await;
+                }
+
+                let profit = self.calculate_actual_profit(trade, leg_a_winner, leg_b_winner);
+                TradeState::Settled { profit }
+            }
+            terminal => terminal.clone(),
+        }
+    }
+
     async fn check_market_result_cached(&self, condition_id: &str, token_id: &str) -> Result<(bool, bool)> {
-        // Check cache first (cache for 60 seconds)
         let cache_ttl = Duration::from_secs(60);
-        let mut cache = self.market_cache.lock().await;
-        
-        // Check if we have cached data that's still valid
-        if let Some(cached) = cache.get(condition_id) {
+
+        // Get (or create) this market's own entry, holding the outer map lock only long
+        // enough to do that - so a slow request for one market never blocks lookups for
+        // another.
+        let entry = {
+            let mut cache = self.market_cache.lock().await;
+            cache.entry(condition_id.to_string())
+                .or_insert_with(|| Arc::new(Mutex::new(CachedMarketEntry::default())))
+                .clone()
+        };
+
+        // Hold this market's entry lock across the cache check *and* the request below, so a
+        // concurrent caller for the same condition_id awaits this lock and reuses our result
+        // instead of firing its own redundant `get_market` call.
+        let mut slot = entry.lock().await;
+
+        if let Some(cached) = &slot.data {
             if cached.cached_at.elapsed() < cache_ttl {
-                // Use cached data
                 let market = &cached.market;
                 if market.closed {
                     let winner = market.tokens.iter()
@@ -132,19 +266,15 @@ impl Trader {
                 }
             }
         }
-        
+
         // Cache miss or expired - fetch from API
-        drop(cache);
         match self.api.get_market(condition_id).await {
             Ok(market) => {
-                // Update cache
-                let mut cache = self.market_cache.lock().await;
-                cache.insert(condition_id.to_string(), CachedMarketData {
+                slot.data = Some(CachedMarketData {
                     market: market.clone(),
                     cached_at: Instant::now(),
                 });
-                drop(cache);
-                
+
                 if market.closed {
                     // Find our token and check if it's the winner
                     let winner = market.tokens.iter()
@@ -164,102 +294,171 @@ impl Trader {
     }
 
     /// Sell winning tokens when markets close (production mode only)
-    async fn sell_winning_tokens(&self, trade: &PendingTrade, eth_winner: bool, btc_winner: bool) {
+    async fn sell_winning_tokens(&self, trade: &PendingTrade, leg_a_winner: bool, leg_b_winner: bool) {
         // When markets close, winning tokens are worth $1 each
         // We should sell them to realize the profit
         let sell_price = "1.0"; // Winning tokens are worth $1 when market closes
-        
-        if eth_winner {
-            // Sell ETH Up token (it won, worth $1)
+
+        if leg_a_winner {
+            // Sell leg A token (it won, worth $1)
             let sell_order = OrderRequest {
-                token_id: trade.eth_token_id.clone(),
+                token_id: trade.leg_a_token_id.clone(),
                 side: "SELL".to_string(),
                 size: format!("{:.6}", trade.units),
                 price: sell_price.to_string(),
                 order_type: "LIMIT".to_string(),
             };
-            
+
             match self.api.place_order(&sell_order).await {
                 Ok(_) => {
-                    info!("✅ Sold {} units of ETH Up token (winner) at $1.00", trade.units);
+                    info!("✅ Sold {} units of leg A token (winner) at $1.00", trade.units);
                 }
                 Err(e) => {
-                    warn!("⚠️  Failed to sell ETH Up token: {}", e);
+                    warn!("⚠️  Failed to sell leg A token: {}", e);
                 }
             }
         }
-        
-        if btc_winner {
-            // Sell BTC Down token (it won, worth $1)
+
+        if leg_b_winner {
+            // Sell leg B token (it won, worth $1)
             let sell_order = OrderRequest {
-                token_id: trade.btc_token_id.clone(),
+                token_id: trade.leg_b_token_id.clone(),
                 side: "SELL".to_string(),
                 size: format!("{:.6}", trade.units),
                 price: sell_price.to_string(),
                 order_type: "LIMIT".to_string(),
             };
-            
+
             match self.api.place_order(&sell_order).await {
                 Ok(_) => {
-                    info!("✅ Sold {} units of BTC Down token (winner) at $1.00", trade.units);
+                    info!("✅ Sold {} units of leg B token (winner) at $1.00", trade.units);
                 }
                 Err(e) => {
-                    warn!("⚠️  Failed to sell BTC Down token: {}", e);
+                    warn!("⚠️  Failed to sell leg B token: {}", e);
                 }
             }
         }
-        
-        if !eth_winner && !btc_winner {
+
+        if !leg_a_winner && !leg_b_winner {
             warn!("⚠️  Both tokens lost - nothing to sell (both worth $0)");
         }
     }
 
-    fn calculate_actual_profit(&self, trade: &PendingTrade, eth_winner: bool, btc_winner: bool) -> f64 {
-        // We bought ETH Up + BTC Down
+    fn calculate_actual_profit(&self, trade: &PendingTrade, leg_a_winner: bool, leg_b_winner: bool) -> f64 {
+        // We bought leg A + leg B
         // When markets close:
-        // - If ETH Up wins: we get $1 per unit
-        // - If BTC Down wins: we get $1 per unit
+        // - If leg A wins: we get $1 per unit
+        // - If leg B wins: we get $1 per unit
         // - If both win: we get $2 per unit
         // - If both lose: we get $0 per unit
-        
-        let payout_per_unit = if eth_winner && btc_winner {
-            2.0 // Both won! (ETH went UP, BTC went DOWN)
-        } else if eth_winner || btc_winner {
+
+        let payout_per_unit = if leg_a_winner && leg_b_winner {
+            2.0 // Both won!
+        } else if leg_a_winner || leg_b_winner {
             1.0 // One won (break even or small profit)
         } else {
-            0.0 // Both lost! (ETH went DOWN, BTC went UP) - TOTAL LOSS
+            0.0 // Both lost! - TOTAL LOSS
         };
-        
+
         let total_payout = payout_per_unit * trade.units;
         let actual_profit = total_payout - trade.investment_amount;
-        
+
         if actual_profit < 0.0 {
             warn!("⚠️  LOSS: Both tokens lost! Lost ${:.4} on this trade", -actual_profit);
         }
-        
+
         actual_profit
     }
 
     /// Execute arbitrage trade
     pub async fn execute_arbitrage(&self, opportunity: &ArbitrageOpportunity) -> Result<()> {
+        if self.resume_only {
+            debug!("--resume-only mode: ignoring new opportunity, only draining existing positions");
+            return Ok(());
+        }
+
+        let position_size = match self.accumulate_position_size(opportunity).await {
+            Some(size) => size,
+            None => return Ok(()),
+        };
+
         if self.simulation_mode {
-            self.simulate_trade(opportunity).await
+            self.simulate_trade(opportunity, position_size).await
         } else {
-            self.execute_real_trade(opportunity).await
+            self.execute_real_trade(opportunity, position_size).await
         }
     }
 
-    async fn simulate_trade(&self, opportunity: &ArbitrageOpportunity) -> Result<()> {
+    /// Accumulate toward `min_position_size` instead of firing a dust-sized order whenever a
+    /// single tick's position size falls short - mirrors enforcing both a minimum and maximum
+    /// buy amount, so order/network fees don't eat the edge on a thin 15-minute market.
+    /// Returns `Some(size)` once the queued amount for this opportunity clears the minimum and
+    /// should be committed now, or `None` while still accumulating.
+    async fn accumulate_position_size(&self, opportunity: &ArbitrageOpportunity) -> Option<f64> {
+        let available_balance = match self.api.get_balance().await {
+            Ok(balance) => f64::try_from(balance).unwrap_or(0.0),
+            Err(e) => {
+                warn!("Failed to fetch usable balance, skipping opportunity: {}", e);
+                return None;
+            }
+        };
+
+        let ideal_size = self.calculate_position_size(opportunity, available_balance);
+        if ideal_size < self.config.dust_threshold {
+            debug!(
+                "Skipping dust-sized opportunity (${:.4} < ${:.2} dust threshold)",
+                ideal_size, self.config.dust_threshold
+            );
+            return None;
+        }
+
+        let trade_key = format!("{}_{}", opportunity.leg_a.condition_id, opportunity.leg_b.condition_id);
+        let mut queued = self.queued_investment.lock().await;
+        let accumulated = queued.entry(trade_key.clone()).or_insert(0.0);
+        *accumulated += ideal_size;
+
+        match Self::commit_if_ready(*accumulated, available_balance, self.config.min_position_size) {
+            Some(committed) => {
+                queued.remove(&trade_key);
+                Some(committed)
+            }
+            None => {
+                debug!(
+                    "Accumulating position for {}: ${:.2} queued / ${:.2} minimum (${:.2} usable now)",
+                    trade_key, accumulated, self.config.min_position_size, available_balance
+                );
+                None
+            }
+        }
+    }
+
+    /// Decide whether the running tally `accumulated` is ready to commit. `accumulated` is
+    /// just a sum of edge seen across ticks, not capital we've actually reserved - each
+    /// `ideal_size` that fed it was already capped to whatever `available_balance` was *at
+    /// that tick*, so summing them blindly would double-count the same balance across ticks
+    /// (e.g. a steady $5 balance recorded twice reads as $10 queued). What we can actually
+    /// commit right now is capped at today's `available_balance`, and only fires once that
+    /// capped amount clears `min_position_size`.
+    fn commit_if_ready(accumulated: f64, available_balance: f64, min_position_size: f64) -> Option<f64> {
+        let committed = accumulated.min(available_balance.max(0.0));
+        if committed < min_position_size {
+            None
+        } else {
+            Some(committed)
+        }
+    }
+
+    async fn simulate_trade(&self, opportunity: &ArbitrageOpportunity, position_size: f64) -> Result<()> {
         info!(
             "🔍 SIMULATION: Arbitrage opportunity detected!"
         );
         info!(
-            "   ETH Up Token Price: ${:.4}",
-            opportunity.eth_up_price
+            "   Leg A ({} {}) Price: ${:.4}",
+            opportunity.leg_a.market_name, opportunity.leg_a.outcome, opportunity.leg_a.price
         );
         info!(
-            "   BTC Down Token Price: ${:.4}",
-            opportunity.btc_down_price
+            "   Leg B ({} {}) Price: ${:.4}",
+            opportunity.leg_b.market_name, opportunity.leg_b.outcome, opportunity.leg_b.price
         );
         info!(
             "   Total Cost: ${:.4}",
@@ -271,58 +470,60 @@ impl Trader {
             (opportunity.expected_profit / opportunity.total_cost) * Decimal::from(100)
         );
         info!(
-            "   ETH Token ID: {}",
-            opportunity.eth_up_token_id
+            "   Leg A Token ID: {}",
+            opportunity.leg_a.token_id
         );
         info!(
-            "   BTC Token ID: {}",
-            opportunity.btc_down_token_id
+            "   Leg B Token ID: {}",
+            opportunity.leg_b.token_id
         );
 
-        // Calculate position size (total dollar amount to invest)
-        let position_size = self.calculate_position_size(opportunity);
         info!("   Position Size: ${:.2} (total investment amount)", position_size);
-        
+
         // Calculate how many units we're buying
         let cost_per_unit = f64::try_from(opportunity.total_cost).unwrap_or(1.0);
         let units = position_size / cost_per_unit;
-        info!("   Units: {:.2} (each unit = ${:.4}, so ${:.2} / ${:.4} = {:.2} units)", 
+        info!("   Units: {:.2} (each unit = ${:.4}, so ${:.2} / ${:.4} = {:.2} units)",
               units, cost_per_unit, position_size, cost_per_unit, units);
-        info!("   ETH Up amount: ${:.2} ({} units × ${:.4})", 
-              units * f64::try_from(opportunity.eth_up_price).unwrap_or(0.0),
-              units, opportunity.eth_up_price);
-        info!("   BTC Down amount: ${:.2} ({} units × ${:.4})", 
-              units * f64::try_from(opportunity.btc_down_price).unwrap_or(0.0),
-              units, opportunity.btc_down_price);
+        info!("   Leg A amount: ${:.2} ({} units × ${:.4})",
+              units * f64::try_from(opportunity.leg_a.price).unwrap_or(0.0),
+              units, opportunity.leg_a.price);
+        info!("   Leg B amount: ${:.2} ({} units × ${:.4})",
+              units * f64::try_from(opportunity.leg_b.price).unwrap_or(0.0),
+              units, opportunity.leg_b.price);
 
         // In simulation mode, we track the trade and will calculate actual profit when markets close
         // Use condition IDs as key - accumulate multiple trades in the same period
-        let trade_key = format!("{}_{}", opportunity.eth_condition_id, opportunity.btc_condition_id);
-        
+        let trade_key = format!("{}_{}", opportunity.leg_a.condition_id, opportunity.leg_b.condition_id);
+
         let mut pending = self.pending_trades.lock().await;
-        
+
         // If we already have a trade for this period, accumulate it (add units and investment)
         if let Some(existing_trade) = pending.get_mut(&trade_key) {
             // Accumulate: add new units and investment to existing trade
             existing_trade.units += units;
             existing_trade.investment_amount += position_size;
-            info!("   📊 Accumulated trade: Total units: {:.2}, Total investment: ${:.2}", 
+            info!("   📊 Accumulated trade: Total units: {:.2}, Total investment: ${:.2}",
                   existing_trade.units, existing_trade.investment_amount);
         } else {
             // First trade for this period - create new entry
             let pending_trade = PendingTrade {
-                eth_token_id: opportunity.eth_up_token_id.clone(),
-                btc_token_id: opportunity.btc_down_token_id.clone(),
-                eth_condition_id: opportunity.eth_condition_id.clone(),
-                btc_condition_id: opportunity.btc_condition_id.clone(),
+                leg_a_token_id: opportunity.leg_a.token_id.clone(),
+                leg_b_token_id: opportunity.leg_b.token_id.clone(),
+                leg_a_condition_id: opportunity.leg_a.condition_id.clone(),
+                leg_b_condition_id: opportunity.leg_b.condition_id.clone(),
                 investment_amount: position_size,
                 units,
                 timestamp: std::time::Instant::now(),
+                state: TradeState::Open,
             };
-            pending.insert(trade_key, pending_trade);
+            pending.insert(trade_key.clone(), pending_trade);
+        }
+        if let Some(trade) = pending.get(&trade_key) {
+            self.persist(&trade_key, trade);
         }
         drop(pending);
-        
+
         let mut trades = self.trades_executed.lock().await;
         *trades += 1;
         let trades_count = *trades;
@@ -338,85 +539,136 @@ impl Trader {
         Ok(())
     }
 
-    async fn execute_real_trade(&self, opportunity: &ArbitrageOpportunity) -> Result<()> {
+    async fn execute_real_trade(&self, opportunity: &ArbitrageOpportunity, position_size: f64) -> Result<()> {
         info!("🚀 PRODUCTION: Executing real arbitrage trade...");
-        
-        let position_size = self.calculate_position_size(opportunity);
-        let size_str = format!("{:.6}", position_size);
 
-        // Place order for ETH Up token
-        let eth_order = OrderRequest {
-            token_id: opportunity.eth_up_token_id.clone(),
+        // `size` on an order is shares, not dollars - matches how `sell_winning_tokens` sizes
+        // its sell order off `trade.units`, not `investment_amount`.
+        let cost_per_unit = f64::try_from(opportunity.total_cost).unwrap_or(1.0);
+        let units = position_size / cost_per_unit;
+        let size_str = format!("{:.6}", units);
+
+        // Place order for leg A token
+        let leg_a_order = OrderRequest {
+            token_id: opportunity.leg_a.token_id.clone(),
             side: "BUY".to_string(),
             size: size_str.clone(),
-            price: opportunity.eth_up_price.to_string(),
+            price: opportunity.leg_a.price.to_string(),
             order_type: "LIMIT".to_string(),
         };
 
-        // Place order for BTC Down token
-        let btc_order = OrderRequest {
-            token_id: opportunity.btc_down_token_id.clone(),
+        // Place order for leg B token
+        let leg_b_order = OrderRequest {
+            token_id: opportunity.leg_b.token_id.clone(),
             side: "BUY".to_string(),
             size: size_str.clone(),
-            price: opportunity.btc_down_price.to_string(),
+            price: opportunity.leg_b.price.to_string(),
             order_type: "LIMIT".to_string(),
         };
 
         // Execute both orders
-        let (eth_result, btc_result) = tokio::join!(
-            self.api.place_order(&eth_order),
-            self.api.place_order(&btc_order)
+        let (leg_a_result, leg_b_result) = tokio::join!(
+            self.api.place_order(&leg_a_order),
+            self.api.place_order(&leg_b_order)
         );
 
-        match eth_result {
+        let leg_a_filled = match &leg_a_result {
             Ok(response) => {
-                info!("ETH Up order placed: {:?}", response);
+                info!("Leg A order placed: {:?}", response);
+                true
             }
             Err(e) => {
-                warn!("Failed to place ETH Up order: {}", e);
+                warn!("Failed to place leg A order: {}", e);
+                false
             }
-        }
+        };
 
-        match btc_result {
+        let leg_b_filled = match &leg_b_result {
             Ok(response) => {
-                info!("BTC Down order placed: {:?}", response);
+                info!("Leg B order placed: {:?}", response);
+                true
             }
             Err(e) => {
-                warn!("Failed to place BTC Down order: {}", e);
+                warn!("Failed to place leg B order: {}", e);
+                false
             }
+        };
+
+        if !leg_a_filled && !leg_b_filled {
+            return Err(anyhow::anyhow!("both legs failed to fill, no position opened"));
         }
 
-        // Track the trade so we can sell tokens when markets close
-        let cost_per_unit = f64::try_from(opportunity.total_cost).unwrap_or(1.0);
-        let units = position_size / cost_per_unit;
-        
+        // Track the trade so we can sell tokens when markets close (units computed above,
+        // alongside the order size)
+
         // Use condition IDs as key - accumulate multiple trades in the same period
-        let trade_key = format!("{}_{}", opportunity.eth_condition_id, opportunity.btc_condition_id);
-        
+        let trade_key = format!("{}_{}", opportunity.leg_a.condition_id, opportunity.leg_b.condition_id);
+
         let mut pending = self.pending_trades.lock().await;
-        
+
+        if !leg_a_filled || !leg_b_filled {
+            // One leg filled and the other didn't - a first-class failure, not a silently
+            // "balanced" position. Recorded under a key distinct from `trade_key` so it can
+            // never clobber a healthy accumulated position already open for this same market
+            // pair/period; left in `pending_trades` as `Failed` for manual review (the filled
+            // leg may still need to be unwound by an operator).
+            let (reason, filled_token_id) = if !leg_a_filled {
+                ("leg A order failed to fill".to_string(), &opportunity.leg_b.token_id)
+            } else {
+                ("leg B order failed to fill".to_string(), &opportunity.leg_a.token_id)
+            };
+            let failed_key = format!("{}_partial_{}", trade_key, filled_token_id);
+
+            warn!(
+                "⚠️  Partial fill for trade {}: {} - recorded separately as {} for manual review",
+                trade_key, reason, failed_key
+            );
+            pending.insert(
+                failed_key.clone(),
+                PendingTrade {
+                    leg_a_token_id: opportunity.leg_a.token_id.clone(),
+                    leg_b_token_id: opportunity.leg_b.token_id.clone(),
+                    leg_a_condition_id: opportunity.leg_a.condition_id.clone(),
+                    leg_b_condition_id: opportunity.leg_b.condition_id.clone(),
+                    investment_amount: position_size,
+                    units,
+                    timestamp: std::time::Instant::now(),
+                    state: TradeState::Failed { reason },
+                },
+            );
+            if let Some(trade) = pending.get(&failed_key) {
+                self.persist(&failed_key, trade);
+            }
+            drop(pending);
+            return Ok(());
+        }
+
         // If we already have a trade for this period, accumulate it (add units and investment)
         if let Some(existing_trade) = pending.get_mut(&trade_key) {
             // Accumulate: add new units and investment to existing trade
             existing_trade.units += units;
             existing_trade.investment_amount += position_size;
-            info!("   📊 Accumulated trade: Total units: {:.2}, Total investment: ${:.2}", 
+            info!("   📊 Accumulated trade: Total units: {:.2}, Total investment: ${:.2}",
                   existing_trade.units, existing_trade.investment_amount);
         } else {
             // First trade for this period - create new entry
             let pending_trade = PendingTrade {
-                eth_token_id: opportunity.eth_up_token_id.clone(),
-                btc_token_id: opportunity.btc_down_token_id.clone(),
-                eth_condition_id: opportunity.eth_condition_id.clone(),
-                btc_condition_id: opportunity.btc_condition_id.clone(),
+                leg_a_token_id: opportunity.leg_a.token_id.clone(),
+                leg_b_token_id: opportunity.leg_b.token_id.clone(),
+                leg_a_condition_id: opportunity.leg_a.condition_id.clone(),
+                leg_b_condition_id: opportunity.leg_b.condition_id.clone(),
                 investment_amount: position_size,
                 units,
                 timestamp: std::time::Instant::now(),
+                state: TradeState::Open,
             };
-            pending.insert(trade_key, pending_trade);
+            pending.insert(trade_key.clone(), pending_trade);
+        }
+        if let Some(trade) = pending.get(&trade_key) {
+            self.persist(&trade_key, trade);
         }
         drop(pending);
-        
+
         let mut trades = self.trades_executed.lock().await;
         *trades += 1;
         let trades_count = *trades;
@@ -432,32 +684,232 @@ impl Trader {
         Ok(())
     }
 
-    fn calculate_position_size(&self, opportunity: &ArbitrageOpportunity) -> f64 {
-        // Position size is the total dollar amount to invest in this arbitrage opportunity
-        // We use max_position_size from config as the maximum investment per trade
-        let max_size = self.config.max_position_size;
+    /// Compute the *ideal* position size for `opportunity` given `available_balance` - this is
+    /// not yet clamped to `min_position_size`; that floor is enforced once, by
+    /// `accumulate_position_size`, which is what actually decides whether to fire now or keep
+    /// accumulating. Clamping it here too would make the accumulation path unreachable.
+    fn calculate_position_size(&self, opportunity: &ArbitrageOpportunity, available_balance: f64) -> f64 {
+        let ideal_size = match self.config.sizing_strategy {
+            SizingStrategy::Flat => self.flat_position_size(opportunity, available_balance),
+            SizingStrategy::FractionalKelly => self.fractional_kelly_position_size(opportunity, available_balance),
+        };
+
+        // Don't size past what the book can actually fill - `blended_cost` is the real dollar
+        // cost `size_against_depth` found walking both legs' ask ladders to their deepest
+        // matched size, so it's the hard ceiling regardless of strategy or available balance.
+        match opportunity.blended_cost {
+            Some(blended_cost) => ideal_size.min(f64::try_from(blended_cost).unwrap_or(ideal_size)),
+            None => ideal_size,
+        }
+    }
+
+    fn flat_position_size(&self, opportunity: &ArbitrageOpportunity, available_balance: f64) -> f64 {
+        // Position size is the total dollar amount to invest in this arbitrage opportunity.
+        // We cap it at max_position_size (our per-trade ceiling) and at available_balance (we
+        // can't invest capital we don't have) - whichever is smaller wins.
+        let max_size = self.config.max_position_size.min(available_balance.max(0.0));
         let cost_per_unit = f64::try_from(opportunity.total_cost).unwrap_or(1.0);
-        
+
         // Calculate how many "units" (pairs of tokens) we can buy with max position size
         // Each unit costs total_cost (e.g., $0.75), so with $100 we can buy 100/0.75 = 133.33 units
         let units = max_size / cost_per_unit;
-        
+
         // The actual position size is: units * cost_per_unit
         // But we cap it at max_size to not exceed our limit
         let position_size = (units * cost_per_unit).min(max_size);
-        
+
         // For example:
         // - If total_cost = $0.75 and max_size = $100
         // - units = 100 / 0.75 = 133.33
         // - position_size = 133.33 * 0.75 = $100 (capped at max_size)
-        // - This means we buy $100 worth of tokens total ($50 ETH Up + $50 BTC Down)
+        // - This means we buy $100 worth of tokens total (split across both legs)
         position_size
     }
 
+    /// Scale the committed amount with the realized edge instead of always deploying
+    /// `max_position_size`, so a 0.5% opportunity gets a fraction of the capital a 20% one
+    /// does. `fraction = (edge * kelly_multiplier).clamp(0, 1)`, where `edge` is
+    /// `expected_profit / total_cost`. Also capped at `available_balance` - we can't invest
+    /// capital we don't have.
+    fn fractional_kelly_position_size(&self, opportunity: &ArbitrageOpportunity, available_balance: f64) -> f64 {
+        let expected_profit = f64::try_from(opportunity.expected_profit).unwrap_or(0.0);
+        let total_cost = f64::try_from(opportunity.total_cost).unwrap_or(1.0);
+        let edge = expected_profit / total_cost;
+
+        let fraction = (edge * self.config.kelly_multiplier).clamp(0.0, 1.0);
+        (self.config.max_position_size * fraction).min(available_balance.max(0.0))
+    }
+
     pub async fn get_stats(&self) -> (f64, u64) {
         let total = *self.total_profit.lock().await;
         let trades = *self.trades_executed.lock().await;
         (total, trades)
     }
+
+    /// Snapshot every open position for the operator CLI's `positions` command
+    pub async fn list_positions(&self) -> Vec<PositionSummary> {
+        let pending = self.pending_trades.lock().await;
+        pending
+            .iter()
+            .map(|(key, trade)| PositionSummary {
+                key: key.clone(),
+                state: trade.state.clone(),
+                age: trade.timestamp.elapsed(),
+                units: trade.units,
+                investment_amount: trade.investment_amount,
+            })
+            .collect()
+    }
+
+    /// Force one position straight to `Settled`, bypassing the normal 14-minute age gate - an
+    /// operator's escape hatch for unsticking a position rather than waiting out the window.
+    /// Runs the same settlement path as `advance`'s `Settling` branch: check both legs'
+    /// results, sell the winning leg(s) in production mode, then fold the actual profit in.
+    pub async fn force_settle(&self, key: &str) -> Result<()> {
+        let mut pending = self.pending_trades.lock().await;
+        let trade = pending
+            .get_mut(key)
+            .ok_or_else(|| anyhow::anyhow!("no pending trade with key {}", key))?;
+
+        let (_, leg_a_winner) = self
+            .check_market_result_cached(&trade.leg_a_condition_id, &trade.leg_a_token_id)
+            .await
+            .unwrap_or((false, false));
+        let (_, leg_b_winner) = self
+            .check_market_result_cached(&trade.leg_b_condition_id, &trade.leg_b_token_id)
+            .await
+            .unwrap_or((false, false));
+
+        if !self.simulation_mode {
+            self.sell_winning_tokens(trade, leg_a_winner, leg_b_winner).await;
+        }
+
+        let profit = self.calculate_actual_profit(trade, leg_a_winner, leg_b_winner);
+
+        let mut total = self.total_profit.lock().await;
+        *total += profit;
+        drop(total);
+
+        pending.remove(key);
+        drop(pending);
+
+        if let Err(e) = self.store.remove(key) {
+            warn!("Failed to remove force-settled trade {} from disk: {}", key, e);
+        }
+
+        info!("💰 Force-settled trade {} | Actual Profit: ${:.4}", key, profit);
+        Ok(())
+    }
+
+    /// Remove a stuck position without settling it - e.g. one an operator has decided to
+    /// unwind manually outside the bot.
+    pub async fn cancel_position(&self, key: &str) -> Result<()> {
+        let mut pending = self.pending_trades.lock().await;
+        if pending.remove(key).is_none() {
+            return Err(anyhow::anyhow!("no pending trade with key {}", key));
+        }
+        drop(pending);
+
+        if let Err(e) = self.store.remove(key) {
+            warn!("Failed to remove cancelled trade {} from disk: {}", key, e);
+        }
+
+        warn!("🗑️  Cancelled position {} (removed without settling)", key);
+        Ok(())
+    }
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::Leg;
+
+    fn test_trader(config: TradingConfig) -> Trader {
+        let path = std::env::temp_dir().join(format!(
+            "trader_test_{}_{}",
+            std::process::id(),
+            std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap()
+                .as_nanos()
+        ));
+        Trader {
+            api: Arc::new(PolymarketApi::new()),
+            config,
+            simulation_mode: true,
+            resume_only: false,
+            total_profit: Arc::new(Mutex::new(0.0)),
+            trades_executed: Arc::new(Mutex::new(0)),
+            pending_trades: Arc::new(Mutex::new(HashMap::new())),
+            market_cache: Arc::new(Mutex::new(HashMap::new())),
+            queued_investment: Arc::new(Mutex::new(HashMap::new())),
+            store: TradeStore::open(path.to_str().unwrap()).unwrap(),
+        }
+    }
+
+    fn make_opportunity(total_cost: f64, expected_profit: f64) -> ArbitrageOpportunity {
+        ArbitrageOpportunity {
+            leg_a: Leg {
+                market_name: "ETH".to_string(),
+                outcome: "Up".to_string(),
+                token_id: "leg-a".to_string(),
+                condition_id: "cond-a".to_string(),
+                price: Decimal::try_from(total_cost / 2.0).unwrap(),
+            },
+            leg_b: Leg {
+                market_name: "ETH".to_string(),
+                outcome: "Down".to_string(),
+                token_id: "leg-b".to_string(),
+                condition_id: "cond-a".to_string(),
+                price: Decimal::try_from(total_cost / 2.0).unwrap(),
+            },
+            total_cost: Decimal::try_from(total_cost).unwrap(),
+            expected_profit: Decimal::try_from(expected_profit).unwrap(),
+            max_size: None,
+            blended_cost: None,
+        }
+    }
+
+    #[test]
+    fn fractional_kelly_clamps_fraction_to_one() {
+        let mut config = TradingConfig::default();
+        config.sizing_strategy = SizingStrategy::FractionalKelly;
+        config.kelly_multiplier = 10.0;
+        config.max_position_size = 100.0;
+        let trader = test_trader(config);
+
+        // edge = 0.5 / 0.5 = 1.0, * kelly_multiplier (10.0) = 10.0, clamped to 1.0
+        let opportunity = make_opportunity(0.5, 0.5);
+        let size = trader.fractional_kelly_position_size(&opportunity, 1000.0);
+
+        assert_eq!(size, 100.0);
+    }
+
+    #[test]
+    fn fractional_kelly_caps_at_available_balance() {
+        let mut config = TradingConfig::default();
+        config.sizing_strategy = SizingStrategy::FractionalKelly;
+        let trader = test_trader(config);
+
+        let opportunity = make_opportunity(0.5, 0.5);
+        let size = trader.fractional_kelly_position_size(&opportunity, 5.0);
+
+        assert_eq!(size, 5.0);
+    }
+
+    #[test]
+    fn commit_if_ready_rejects_balance_capped_double_count() {
+        // Reproduces the review scenario: two ticks each saw a steady $5 balance, so the
+        // queued sum reads as $10 even though only $5 of capital is actually usable.
+        let accumulated = 5.0 + 5.0;
+
+        assert_eq!(Trader::commit_if_ready(accumulated, 5.0, 10.0), None);
+    }
+
+    #[test]
+    fn commit_if_ready_fires_once_balance_genuinely_clears_minimum() {
+        let accumulated = 5.0 + 5.0;
+
+        assert_eq!(Trader::commit_if_ready(accumulated, 12.0, 10.0), Some(10.0));
+    }
+}
@@ -0,0 +1,140 @@
+use crate::models::{BookLevel, TokenPrice};
+use anyhow::{anyhow, Result};
+use futures_util::{SinkExt, StreamExt};
+use log::{debug, warn};
+use rust_decimal::Decimal;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::{mpsc, Mutex};
+use tokio_tungstenite::{connect_async, tungstenite::Message};
+
+const MARKET_WS_URL: &str = "wss://ws-subscriptions-clob.polymarket.com/ws/market";
+
+// If the stream stalls without the socket actually dropping (frames just stop arriving), a
+// cached price with no expiry would keep looking "live" forever. Treat anything older than
+// this as unusable so callers fall back to REST instead of trading on a stale quote.
+const STALE_AFTER: Duration = Duration::from_secs(30);
+
+/// A single ticker frame pushed by Polymarket's market channel. `asks`/`bids` mirror a
+/// Kraken-style ticker's `a`/`b` arrays: each level is `[price, size]`, best price first.
+#[derive(Debug, Deserialize)]
+struct TickerData {
+    asset_id: String,
+    #[serde(default)]
+    asks: Vec<[String; 2]>,
+    #[serde(default)]
+    bids: Vec<[String; 2]>,
+}
+
+/// Maintains the last-known bid/ask per token, fed by a live WebSocket subscription to
+/// Polymarket's market channel. REST remains the fallback: `PolymarketApi::get_market` for
+/// initial token-ID discovery, and `PolymarketApi::get_price` for any token without a fresh
+/// streamed price (e.g. gaps immediately after a reconnect).
+pub struct PriceStream {
+    last_known: Arc<Mutex<HashMap<String, (TokenPrice, Instant)>>>,
+}
+
+impl PriceStream {
+    pub fn new() -> Self {
+        Self {
+            last_known: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// Returns the cached price if one exists and is fresher than `STALE_AFTER`; a missing or
+    /// stale entry returns `None` so the caller (`MarketMonitor::fetch_token_price`) falls back
+    /// to REST rather than trading on a quote the stream silently stopped updating.
+    pub async fn last_price(&self, token_id: &str) -> Option<TokenPrice> {
+        let last_known = self.last_known.lock().await;
+        let (price, updated_at) = last_known.get(token_id)?;
+        if updated_at.elapsed() > STALE_AFTER {
+            return None;
+        }
+        Some(price.clone())
+    }
+
+    /// Connect, subscribe to `token_ids`, and push every ticker update into the shared
+    /// last-known-price map, notifying `on_update` with the updated token_id. Runs until
+    /// the connection drops or `on_update`'s receiver is dropped. A dropped connection returns
+    /// `Err`, or `Ok(())` when the stream simply ends; either way callers need to reconnect
+    /// (with a REST gap-fill).
+    pub async fn run(&self, token_ids: &[String], on_update: mpsc::Sender<String>) -> Result<()> {
+        if token_ids.is_empty() {
+            return Ok(());
+        }
+
+        let (ws_stream, _) = connect_async(MARKET_WS_URL).await?;
+        let (mut write, mut read) = ws_stream.split();
+
+        let subscribe = serde_json::json!({
+            "type": "market",
+            "assets_ids": token_ids,
+        });
+        write.send(Message::Text(subscribe.to_string())).await?;
+
+        while let Some(msg) = read.next().await {
+            let msg = msg?;
+            let text = match msg {
+                Message::Text(text) => text,
+                Message::Ping(_) | Message::Pong(_) => continue,
+                Message::Close(_) => return Err(anyhow!("market websocket closed by server")),
+                _ => continue,
+            };
+
+            let ticker: TickerData = match serde_json::from_str(&text) {
+                Ok(t) => t,
+                Err(e) => {
+                    debug!("skipping unparseable market frame: {}", e);
+                    continue;
+                }
+            };
+
+            let ask_levels: Vec<BookLevel> = ticker
+                .asks
+                .iter()
+                .filter_map(|level| {
+                    let price = Decimal::from_str(&level[0]).ok()?;
+                    let size = Decimal::from_str(&level[1]).ok()?;
+                    Some(BookLevel { price, size })
+                })
+                .collect();
+            let ask = ask_levels.first().map(|l| l.price);
+            let bid = ticker
+                .bids
+                .first()
+                .and_then(|level| Decimal::from_str(&level[0]).ok());
+
+            if ask.is_none() && bid.is_none() {
+                continue;
+            }
+
+            let price = TokenPrice {
+                token_id: ticker.asset_id.clone(),
+                bid,
+                ask,
+                ask_levels,
+            };
+
+            self.last_known
+                .lock()
+                .await
+                .insert(ticker.asset_id.clone(), (price, Instant::now()));
+
+            if on_update.send(ticker.asset_id).await.is_err() {
+                break; // receiver dropped, nothing left to notify
+            }
+        }
+
+        warn!("market websocket stream ended, reconnect required");
+        Ok(())
+    }
+}
+
+impl Default for PriceStream {
+    fn default() -> Self {
+        Self::new()
+    }
+}